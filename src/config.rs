@@ -0,0 +1,17 @@
+use std::env;
+
+/// Runtime configuration for the catalog service, resolved once at startup
+/// so the binary isn't locked to a single hardcoded data file.
+pub struct Config {
+    pub database_url: String,
+}
+
+impl Config {
+    /// Reads configuration from the environment, falling back to a sane
+    /// default so the service still runs out of the box in development.
+    pub fn from_env() -> Self {
+        let database_url = env::var("DATABASE_URL").unwrap_or_else(|_| "books.db".to_string());
+
+        Self { database_url }
+    }
+}
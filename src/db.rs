@@ -0,0 +1,489 @@
+use rusqlite::{params, Connection, OptionalExtension};
+
+use crate::error::ResponseError;
+use crate::fuzzy;
+use crate::models::{Book, Loan, ModifyBook, Page};
+
+/// SQLite-backed catalog store. Replaces the old read-whole-file-then-
+/// rewrite-whole-file `books.json` approach with atomic, indexed queries.
+pub struct Books {
+    conn: Connection,
+}
+
+impl Books {
+    pub fn new(db_path: &str) -> Self {
+        let conn = Connection::open(db_path).expect("failed to open database");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS books (
+                isbn TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                author TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP
+            )",
+            [],
+        )
+        .expect("failed to create books table");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS loans (
+                isbn TEXT PRIMARY KEY REFERENCES books(isbn),
+                borrower TEXT NOT NULL,
+                borrowed_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+            [],
+        )
+        .expect("failed to create loans table");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS categories (
+                name TEXT PRIMARY KEY
+            )",
+            [],
+        )
+        .expect("failed to create categories table");
+
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS book_categories (
+                isbn TEXT NOT NULL REFERENCES books(isbn),
+                category TEXT NOT NULL REFERENCES categories(name),
+                PRIMARY KEY (isbn, category)
+            )",
+            [],
+        )
+        .expect("failed to create book_categories table");
+
+        Self { conn }
+    }
+
+    pub fn get_all(&self) -> Vec<Book> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT isbn, name, author FROM books")
+            .expect("failed to prepare statement");
+
+        stmt.query_map([], Self::row_to_book)
+            .expect("failed to query books")
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    pub fn find(&self, isbn: &str) -> Result<Book, ResponseError> {
+        self.conn
+            .query_row(
+                "SELECT isbn, name, author FROM books WHERE isbn = ?1",
+                params![isbn],
+                Self::row_to_book,
+            )
+            .optional()
+            .expect("failed to query book")
+            .ok_or_else(|| ResponseError::not_found(isbn))
+    }
+
+    /// Inserts a book and assigns it to `categories`, all inside a single
+    /// transaction: if any category is unknown, the insert is rolled back
+    /// rather than left partially applied.
+    pub fn add(&mut self, book: Book, categories: &[String]) -> Result<Book, ResponseError> {
+        let tx = self.conn.transaction().expect("failed to start transaction");
+
+        let changed = tx
+            .execute(
+                "INSERT INTO books (isbn, name, author) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(isbn) DO NOTHING",
+                params![book.isbn, book.name, book.author],
+            )
+            .expect("failed to insert book");
+
+        if changed == 0 {
+            return Err(ResponseError::already_exists(&book.isbn));
+        }
+
+        for category in categories {
+            Self::assign_category_in(&tx, &book.isbn, category)?;
+        }
+
+        tx.commit().expect("failed to commit transaction");
+
+        Ok(book)
+    }
+
+    pub fn remove(&mut self, isbn: &str) -> Result<Book, ResponseError> {
+        let book = self.find(isbn)?;
+
+        if self.loan_exists(isbn) {
+            return Err(ResponseError::book_on_loan(isbn));
+        }
+
+        self.conn
+            .execute("DELETE FROM books WHERE isbn = ?1", params![isbn])
+            .expect("failed to delete book");
+
+        Ok(book)
+    }
+
+    /// Applies `changes` and assigns any new categories inside a single
+    /// transaction: if a category is unknown, the field changes are rolled
+    /// back rather than left partially applied.
+    pub fn update(&mut self, isbn: &str, changes: ModifyBook) -> Result<Book, ResponseError> {
+        let current = self.find(isbn)?;
+
+        let name = changes.name.unwrap_or(current.name);
+        let author = changes.author.unwrap_or(current.author);
+
+        let tx = self.conn.transaction().expect("failed to start transaction");
+
+        tx.execute(
+            "UPDATE books SET name = ?1, author = ?2, updated_at = CURRENT_TIMESTAMP
+             WHERE isbn = ?3",
+            params![name, author, isbn],
+        )
+        .expect("failed to update book");
+
+        if let Some(categories) = changes.categories {
+            for category in categories {
+                Self::assign_category_in(&tx, isbn, &category)?;
+            }
+        }
+
+        tx.commit().expect("failed to commit transaction");
+
+        Ok(Book {
+            isbn: isbn.to_string(),
+            name,
+            author,
+        })
+    }
+
+    pub fn get_all_paginated(&self, offset: usize, limit: usize) -> Page<Book> {
+        let total = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM books", [], |row| row.get(0))
+            .expect("failed to count books");
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT isbn, name, author FROM books LIMIT ?1 OFFSET ?2")
+            .expect("failed to prepare statement");
+
+        let results = stmt
+            .query_map(params![limit as i64, offset as i64], Self::row_to_book)
+            .expect("failed to query books")
+            .filter_map(Result::ok)
+            .collect();
+
+        Page {
+            results,
+            offset,
+            limit,
+            total,
+        }
+    }
+
+    pub fn search(&self, query: &str) -> Result<Vec<Book>, ResponseError> {
+        let mut scored: Vec<(u32, Book)> = self
+            .get_all()
+            .into_iter()
+            .filter_map(|book| {
+                let score = fuzzy::score_book(&book, query);
+                (score > 0).then_some((score, book))
+            })
+            .collect();
+
+        if scored.is_empty() {
+            return Err(ResponseError::no_search_results(query));
+        }
+
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+        Ok(scored.into_iter().map(|(_, book)| book).collect())
+    }
+
+    pub fn search_paginated(
+        &self,
+        query: &str,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Page<Book>, ResponseError> {
+        let found = self.search(query)?;
+        let total = found.len();
+        let results = found.into_iter().skip(offset).take(limit).collect();
+
+        Ok(Page {
+            results,
+            offset,
+            limit,
+            total,
+        })
+    }
+
+    pub fn borrow(&mut self, isbn: &str, borrower: &str) -> Result<Loan, ResponseError> {
+        self.find(isbn)?;
+
+        let changed = self
+            .conn
+            .execute(
+                "INSERT INTO loans (isbn, borrower) VALUES (?1, ?2)
+                 ON CONFLICT(isbn) DO NOTHING",
+                params![isbn, borrower],
+            )
+            .expect("failed to insert loan");
+
+        if changed == 0 {
+            return Err(ResponseError::already_borrowed(isbn));
+        }
+
+        self.find_loan(isbn)
+    }
+
+    pub fn return_book(&mut self, isbn: &str) -> Result<Loan, ResponseError> {
+        let loan = self.find_loan(isbn)?;
+
+        self.conn
+            .execute("DELETE FROM loans WHERE isbn = ?1", params![isbn])
+            .expect("failed to delete loan");
+
+        Ok(loan)
+    }
+
+    pub fn list_loans(&self) -> Vec<Loan> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT isbn, borrower, borrowed_at FROM loans")
+            .expect("failed to prepare statement");
+
+        stmt.query_map([], Self::row_to_loan)
+            .expect("failed to query loans")
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    fn find_loan(&self, isbn: &str) -> Result<Loan, ResponseError> {
+        self.conn
+            .query_row(
+                "SELECT isbn, borrower, borrowed_at FROM loans WHERE isbn = ?1",
+                params![isbn],
+                Self::row_to_loan,
+            )
+            .optional()
+            .expect("failed to query loan")
+            .ok_or_else(|| ResponseError::not_borrowed(isbn))
+    }
+
+    /// `loans.isbn` references `books(isbn)` with no `ON DELETE` action, so
+    /// deleting a book still on loan would otherwise fail the FK check.
+    fn loan_exists(&self, isbn: &str) -> bool {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM loans WHERE isbn = ?1",
+                params![isbn],
+                |_| Ok(()),
+            )
+            .optional()
+            .expect("failed to query loan")
+            .is_some()
+    }
+
+    pub fn new_category(&mut self, name: &str) -> Result<(), ResponseError> {
+        let changed = self
+            .conn
+            .execute(
+                "INSERT INTO categories (name) VALUES (?1) ON CONFLICT(name) DO NOTHING",
+                params![name],
+            )
+            .expect("failed to insert category");
+
+        if changed == 0 {
+            return Err(ResponseError::category_already_exists(name));
+        }
+
+        Ok(())
+    }
+
+    pub fn del_category(&mut self, name: &str) -> Result<(), ResponseError> {
+        if !self.category_exist(name) {
+            return Err(ResponseError::category_not_found(name));
+        }
+
+        if self.category_in_use(name) {
+            return Err(ResponseError::category_in_use(name));
+        }
+
+        self.conn
+            .execute("DELETE FROM categories WHERE name = ?1", params![name])
+            .expect("failed to delete category");
+
+        Ok(())
+    }
+
+    pub fn category_exist(&self, name: &str) -> bool {
+        Self::category_exist_in(&self.conn, name)
+    }
+
+    /// `book_categories.category` references `categories(name)` with no
+    /// `ON DELETE` action, so deleting a category still assigned to a book
+    /// would otherwise fail the FK check.
+    fn category_in_use(&self, name: &str) -> bool {
+        self.conn
+            .query_row(
+                "SELECT 1 FROM book_categories WHERE category = ?1",
+                params![name],
+                |_| Ok(()),
+            )
+            .optional()
+            .expect("failed to query book_categories")
+            .is_some()
+    }
+
+    /// Shared by the plain connection and by the transactions `add`/`update`
+    /// run their category assignments through.
+    fn category_exist_in(conn: &Connection, name: &str) -> bool {
+        conn.query_row(
+            "SELECT 1 FROM categories WHERE name = ?1",
+            params![name],
+            |_| Ok(()),
+        )
+        .optional()
+        .expect("failed to query category")
+        .is_some()
+    }
+
+    fn assign_category_in(conn: &Connection, isbn: &str, category: &str) -> Result<(), ResponseError> {
+        if !Self::category_exist_in(conn, category) {
+            return Err(ResponseError::category_not_found(category));
+        }
+
+        conn.execute(
+            "INSERT INTO book_categories (isbn, category) VALUES (?1, ?2)
+             ON CONFLICT(isbn, category) DO NOTHING",
+            params![isbn, category],
+        )
+        .expect("failed to assign category");
+
+        Ok(())
+    }
+
+    pub fn list_categories(&self) -> Vec<String> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT name FROM categories")
+            .expect("failed to prepare statement");
+
+        stmt.query_map([], |row| row.get(0))
+            .expect("failed to query categories")
+            .filter_map(Result::ok)
+            .collect()
+    }
+
+    pub fn books_in_category(&self, category: &str) -> Result<Vec<Book>, ResponseError> {
+        if !self.category_exist(category) {
+            return Err(ResponseError::category_not_found(category));
+        }
+
+        let mut stmt = self
+            .conn
+            .prepare(
+                "SELECT b.isbn, b.name, b.author FROM books b
+                 JOIN book_categories bc ON bc.isbn = b.isbn
+                 WHERE bc.category = ?1",
+            )
+            .expect("failed to prepare statement");
+
+        Ok(stmt
+            .query_map(params![category], Self::row_to_book)
+            .expect("failed to query books")
+            .filter_map(Result::ok)
+            .collect())
+    }
+
+    fn row_to_book(row: &rusqlite::Row) -> rusqlite::Result<Book> {
+        Ok(Book {
+            isbn: row.get(0)?,
+            name: row.get(1)?,
+            author: row.get(2)?,
+        })
+    }
+
+    fn row_to_loan(row: &rusqlite::Row) -> rusqlite::Result<Loan> {
+        Ok(Loan {
+            isbn: row.get(0)?,
+            borrower: row.get(1)?,
+            borrowed_at: row.get(2)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_books() -> Books {
+        Books::new(":memory:")
+    }
+
+    fn book(isbn: &str, name: &str) -> Book {
+        Book {
+            isbn: isbn.to_string(),
+            name: name.to_string(),
+            author: "Author".to_string(),
+        }
+    }
+
+    #[test]
+    fn get_all_paginated_slices_and_reports_total() {
+        let mut books = test_books();
+        for i in 0..5 {
+            books.add(book(&i.to_string(), "Title"), &[]).unwrap();
+        }
+
+        let page = books.get_all_paginated(1, 2);
+
+        assert_eq!(page.results.len(), 2);
+        assert_eq!(page.total, 5);
+        assert_eq!(page.offset, 1);
+        assert_eq!(page.limit, 2);
+    }
+
+    #[test]
+    fn get_all_paginated_offset_past_end_is_empty_but_keeps_total() {
+        let mut books = test_books();
+        books.add(book("1", "Title"), &[]).unwrap();
+
+        let page = books.get_all_paginated(10, 5);
+
+        assert!(page.results.is_empty());
+        assert_eq!(page.total, 1);
+    }
+
+    #[test]
+    fn get_all_paginated_limit_zero_returns_no_results() {
+        let mut books = test_books();
+        books.add(book("1", "Title"), &[]).unwrap();
+
+        let page = books.get_all_paginated(0, 0);
+
+        assert!(page.results.is_empty());
+        assert_eq!(page.total, 1);
+    }
+
+    #[test]
+    fn search_paginated_slices_ranked_results() {
+        let mut books = test_books();
+        books.add(book("1", "Dune"), &[]).unwrap();
+        books.add(book("2", "Dune Messiah"), &[]).unwrap();
+
+        let page = books.search_paginated("dune", 0, 1).unwrap();
+
+        assert_eq!(page.results.len(), 1);
+        assert_eq!(page.total, 2);
+    }
+
+    #[test]
+    fn search_paginated_no_matches_errors() {
+        let books = test_books();
+
+        let result = books.search_paginated("nonexistent", 0, 10);
+
+        assert!(result.is_err());
+    }
+}
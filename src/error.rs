@@ -0,0 +1,160 @@
+use rocket::http::{ContentType, Status};
+use rocket::response::{self, Responder, Response};
+use rocket::Request;
+use serde::Serialize;
+use std::io::Cursor;
+
+/// Stable, machine-readable identifiers clients can branch on instead of
+/// parsing `message`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+    BookNotFound,
+    BookAlreadyExists,
+    ExternalLookupFailed,
+    InvalidIsbn,
+    BookAlreadyBorrowed,
+    BookNotBorrowed,
+    BookOnLoan,
+    CategoryAlreadyExists,
+    CategoryNotFound,
+    CategoryInUse,
+}
+
+impl ErrorCode {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BookNotFound => "book_not_found",
+            ErrorCode::BookAlreadyExists => "book_already_exists",
+            ErrorCode::ExternalLookupFailed => "external_lookup_failed",
+            ErrorCode::InvalidIsbn => "invalid_isbn",
+            ErrorCode::BookAlreadyBorrowed => "book_already_borrowed",
+            ErrorCode::BookNotBorrowed => "book_not_borrowed",
+            ErrorCode::BookOnLoan => "book_on_loan",
+            ErrorCode::CategoryAlreadyExists => "category_already_exists",
+            ErrorCode::CategoryNotFound => "category_not_found",
+            ErrorCode::CategoryInUse => "category_in_use",
+        }
+    }
+
+    fn status(self) -> Status {
+        match self {
+            ErrorCode::BookNotFound => Status::NotFound,
+            ErrorCode::BookAlreadyExists => Status::Conflict,
+            ErrorCode::ExternalLookupFailed => Status::BadGateway,
+            ErrorCode::InvalidIsbn => Status::BadRequest,
+            ErrorCode::BookAlreadyBorrowed => Status::Conflict,
+            ErrorCode::BookNotBorrowed => Status::Conflict,
+            ErrorCode::BookOnLoan => Status::Conflict,
+            ErrorCode::CategoryAlreadyExists => Status::Conflict,
+            ErrorCode::CategoryNotFound => Status::NotFound,
+            ErrorCode::CategoryInUse => Status::Conflict,
+        }
+    }
+}
+
+/// A catalog error with enough detail for a client to tell "not found"
+/// apart from "already exists" and react accordingly, and enough for
+/// Rocket to answer with the right HTTP status.
+#[derive(Debug, Serialize)]
+pub struct ResponseError {
+    pub message: String,
+    pub code: &'static str,
+    pub error_type: &'static str,
+    #[serde(skip)]
+    pub status: Status,
+}
+
+impl ResponseError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            code: code.as_str(),
+            error_type: "book_api_error",
+            status: code.status(),
+        }
+    }
+
+    pub fn not_found(isbn: &str) -> Self {
+        Self::new(
+            ErrorCode::BookNotFound,
+            format!("Book with ISBN {} not found", isbn),
+        )
+    }
+
+    pub fn already_exists(isbn: &str) -> Self {
+        Self::new(
+            ErrorCode::BookAlreadyExists,
+            format!("Book with ISBN {} already exists", isbn),
+        )
+    }
+
+    pub fn external_lookup_failed(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::ExternalLookupFailed, message)
+    }
+
+    pub fn invalid_isbn(isbn: &str) -> Self {
+        Self::new(ErrorCode::InvalidIsbn, format!("Invalid ISBN: {}", isbn))
+    }
+
+    pub fn no_search_results(query: &str) -> Self {
+        Self::new(
+            ErrorCode::BookNotFound,
+            format!("No book found matching \"{}\"", query),
+        )
+    }
+
+    pub fn already_borrowed(isbn: &str) -> Self {
+        Self::new(
+            ErrorCode::BookAlreadyBorrowed,
+            format!("Book with ISBN {} is already on loan", isbn),
+        )
+    }
+
+    pub fn not_borrowed(isbn: &str) -> Self {
+        Self::new(
+            ErrorCode::BookNotBorrowed,
+            format!("Book with ISBN {} is not currently on loan", isbn),
+        )
+    }
+
+    pub fn book_on_loan(isbn: &str) -> Self {
+        Self::new(
+            ErrorCode::BookOnLoan,
+            format!("Book with ISBN {} is currently on loan and cannot be removed", isbn),
+        )
+    }
+
+    pub fn category_already_exists(name: &str) -> Self {
+        Self::new(
+            ErrorCode::CategoryAlreadyExists,
+            format!("Category {} already exists", name),
+        )
+    }
+
+    pub fn category_not_found(name: &str) -> Self {
+        Self::new(
+            ErrorCode::CategoryNotFound,
+            format!("Category {} not found", name),
+        )
+    }
+
+    pub fn category_in_use(name: &str) -> Self {
+        Self::new(
+            ErrorCode::CategoryInUse,
+            format!("Category {} is still assigned to one or more books", name),
+        )
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ResponseError {
+    fn respond_to(self, _request: &'r Request<'_>) -> response::Result<'static> {
+        let status = self.status;
+        let body = serde_json::to_string(&self).unwrap_or_else(|_| self.message.clone());
+
+        Response::build()
+            .status(status)
+            .header(ContentType::JSON)
+            .sized_body(body.len(), Cursor::new(body))
+            .ok()
+    }
+}
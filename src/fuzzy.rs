@@ -0,0 +1,132 @@
+use crate::models::Book;
+
+const TITLE_WEIGHT: u32 = 2;
+const AUTHOR_WEIGHT: u32 = 1;
+
+/// Score a book against a query for the fuzzy `/search` endpoint: lowercase
+/// both sides, split into word tokens, and sum per-query-word contributions
+/// (exact match > prefix match > fuzzy match within an edit-distance
+/// threshold), weighting title matches higher than author matches.
+pub fn score_book(book: &Book, query: &str) -> u32 {
+    let query = query.to_lowercase();
+    let query_words: Vec<&str> = query.split_whitespace().collect();
+
+    let name = book.name.to_lowercase();
+    let author = book.author.to_lowercase();
+    let name_tokens: Vec<&str> = name.split_whitespace().collect();
+    let author_tokens: Vec<&str> = author.split_whitespace().collect();
+
+    query_words
+        .iter()
+        .map(|word| {
+            let name_score = best_word_score(word, &name_tokens) * TITLE_WEIGHT;
+            let author_score = best_word_score(word, &author_tokens) * AUTHOR_WEIGHT;
+            name_score.max(author_score)
+        })
+        .sum()
+}
+
+/// Best match of `query_word` against any token, 0 if none of them are
+/// close enough to count as a match at all.
+fn best_word_score(query_word: &str, tokens: &[&str]) -> u32 {
+    tokens
+        .iter()
+        .filter_map(|token| word_score(query_word, token))
+        .max()
+        .unwrap_or(0)
+}
+
+fn word_score(query_word: &str, token: &str) -> Option<u32> {
+    if token == query_word {
+        return Some(3);
+    }
+
+    if token.starts_with(query_word) {
+        return Some(2);
+    }
+
+    let threshold = if query_word.chars().count() <= 5 { 1 } else { 2 };
+    if levenshtein(query_word, token) <= threshold {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Classic Levenshtein edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0usize; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1).min(curr[j - 1] + 1).min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book(name: &str, author: &str) -> Book {
+        Book {
+            isbn: "0".to_string(),
+            name: name.to_string(),
+            author: author.to_string(),
+        }
+    }
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("dune", "dune"), 0);
+        assert_eq!(levenshtein("dune", "dun"), 1);
+        assert_eq!(levenshtein("dune", "dunne"), 1);
+        assert_eq!(levenshtein("dune", "done"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn word_score_ranks_exact_above_prefix_above_fuzzy() {
+        assert_eq!(word_score("dune", "dune"), Some(3));
+        assert_eq!(word_score("dun", "dune"), Some(2));
+        assert_eq!(word_score("dune", "dunne"), Some(1));
+    }
+
+    #[test]
+    fn word_score_within_threshold_for_short_words() {
+        // "dune" has 4 chars, so threshold is 1.
+        assert_eq!(word_score("dune", "done"), Some(1));
+        assert_eq!(word_score("dune", "drive"), None);
+    }
+
+    #[test]
+    fn word_score_within_threshold_for_long_words() {
+        // "messiah" has 7 chars, so threshold is 2.
+        assert_eq!(word_score("messiah", "messica"), Some(1));
+        assert_eq!(word_score("messiah", "message"), None);
+    }
+
+    #[test]
+    fn score_book_weights_title_over_author() {
+        let title_match = book("Dune", "Someone Else");
+        let author_match = book("Unrelated", "Dune");
+
+        assert!(score_book(&title_match, "dune") > score_book(&author_match, "dune"));
+    }
+
+    #[test]
+    fn score_book_no_match_is_zero() {
+        let book = book("Dune", "Frank Herbert");
+
+        assert_eq!(score_book(&book, "xyzzy"), 0);
+    }
+}
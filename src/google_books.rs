@@ -0,0 +1,56 @@
+use serde_json::Value;
+
+use crate::error::ResponseError;
+use crate::models::Book;
+
+/// Look up a book's title and author from the Google Books API by ISBN.
+///
+/// Transport failures and a body that is not valid JSON map to
+/// `external_lookup_failed`; an empty or absent `items` array (no match for
+/// the ISBN), or a first item with no `volumeInfo` title, maps to
+/// `book_not_found`. A listing with no `authors` field falls back to
+/// `"Unknown"` rather than failing the whole lookup.
+pub async fn get_book(isbn: &str) -> Result<Book, ResponseError> {
+    let url = format!(
+        "https://www.googleapis.com/books/v1/volumes?q=isbn:{}",
+        isbn
+    );
+
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| ResponseError::external_lookup_failed(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| ResponseError::external_lookup_failed(e.to_string()))?;
+
+    let data: Value = serde_json::from_str(&response)
+        .map_err(|e| ResponseError::external_lookup_failed(e.to_string()))?;
+
+    let item = data["items"]
+        .as_array()
+        .and_then(|items| items.first())
+        .ok_or_else(|| ResponseError::not_found(isbn))?;
+
+    let volume_info = item["volumeInfo"]
+        .as_object()
+        .ok_or_else(|| ResponseError::not_found(isbn))?;
+
+    let name = volume_info
+        .get("title")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ResponseError::not_found(isbn))?
+        .to_string();
+
+    let author = volume_info
+        .get("authors")
+        .and_then(|authors| authors.get(0))
+        .and_then(Value::as_str)
+        .unwrap_or("Unknown")
+        .to_string();
+
+    Ok(Book {
+        isbn: isbn.to_string(),
+        name,
+        author,
+    })
+}
@@ -0,0 +1,96 @@
+/// Validates ISBN-10 and ISBN-13 check digits. Hyphens and spaces are
+/// ignored so both `"0-306-40615-2"` and `"0306406152"` are accepted.
+pub fn is_valid(isbn: &str) -> bool {
+    let cleaned: String = isbn.chars().filter(|c| *c != '-' && *c != ' ').collect();
+
+    match cleaned.len() {
+        10 => is_valid_isbn10(&cleaned),
+        13 => is_valid_isbn13(&cleaned),
+        _ => false,
+    }
+}
+
+fn is_valid_isbn10(isbn: &str) -> bool {
+    let chars: Vec<char> = isbn.chars().collect();
+
+    if !chars[..9].iter().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let last = chars[9];
+    let last_value = match last {
+        'X' | 'x' => 10,
+        c if c.is_ascii_digit() => c.to_digit(10).unwrap(),
+        _ => return false,
+    };
+
+    let sum: u32 = chars[..9]
+        .iter()
+        .enumerate()
+        .map(|(i, c)| (i as u32 + 1) * c.to_digit(10).unwrap())
+        .sum::<u32>()
+        + 10 * last_value;
+
+    sum.is_multiple_of(11)
+}
+
+fn is_valid_isbn13(isbn: &str) -> bool {
+    if !isbn.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let sum: u32 = isbn
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let digit = c.to_digit(10).unwrap();
+            if i % 2 == 0 {
+                digit
+            } else {
+                digit * 3
+            }
+        })
+        .sum();
+
+    sum.is_multiple_of(10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_isbn10() {
+        assert!(is_valid("0306406152"));
+        assert!(is_valid("0-306-40615-2"));
+    }
+
+    #[test]
+    fn valid_isbn10_with_x_check_digit() {
+        assert!(is_valid("097522980X"));
+        assert!(is_valid("097522980x"));
+    }
+
+    #[test]
+    fn valid_isbn13() {
+        assert!(is_valid("9780306406157"));
+    }
+
+    #[test]
+    fn invalid_check_digit() {
+        assert!(!is_valid("0306406153"));
+        assert!(!is_valid("9780306406158"));
+    }
+
+    #[test]
+    fn invalid_length() {
+        assert!(!is_valid("12345"));
+        assert!(!is_valid(""));
+    }
+
+    #[test]
+    fn invalid_non_digit_characters() {
+        assert!(!is_valid("030640615Z"));
+        assert!(!is_valid("abcdefghijklm"));
+    }
+}
@@ -1,183 +1,161 @@
-use rocket::response::status::BadRequest;
 use rocket::serde::json::Json;
 use rocket::State;
-use serde::Deserialize;
-use serde::Serialize;
-use serde_json::Value;
-use std::fs;
 use std::sync::Mutex;
 
 #[macro_use]
 extern crate rocket;
 
-#[derive(Deserialize, Debug, Serialize)]
-struct Error {
-    message: String,
-}
+mod config;
+mod db;
+mod error;
+mod fuzzy;
+mod google_books;
+mod isbn;
+mod models;
 
-#[derive(Deserialize, Debug, Serialize, Clone)]
-struct Book {
-    isbn: String,
-    name: String,
-    author: String,
-}
+use config::Config;
+use db::Books;
+use error::ResponseError;
+use models::{Book, Loan, ModifyBook, Page};
 
-struct Books(Vec<Book>);
+/// Page size used when the caller doesn't supply `limit`.
+const DEFAULT_LIMIT: usize = 20;
 
-impl Books {
-    fn new() -> Self {
-        Self(load_books("books.json"))
-    }
+#[get("/?<offset>&<limit>")]
+fn get_all(
+    books: &State<Mutex<Books>>,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Json<Page<Book>> {
+    Json(
+        books
+            .lock()
+            .unwrap()
+            .get_all_paginated(offset.unwrap_or(0), limit.unwrap_or(DEFAULT_LIMIT)),
+    )
+}
 
-    fn get_all(&self) -> Vec<Book> {
-        self.0.clone()
+#[get("/get/<isbn>")]
+fn get(books: &State<Mutex<Books>>, isbn: &str) -> Result<Json<Book>, ResponseError> {
+    if !isbn::is_valid(isbn) {
+        return Err(ResponseError::invalid_isbn(isbn));
     }
 
-    fn find(&self, isbn: String) -> Result<Book, Error> {
-        for book in &self.0 {
-            if isbn == book.isbn {
-                return Ok(book.clone());
-            }
-        }
+    books.lock().unwrap().find(isbn).map(Json)
+}
 
-        Err(Error {
-            message: format!("Book with ISBN {} not found", isbn),
-        })
-    }
+#[get("/search/<q>?<offset>&<limit>")]
+fn search(
+    books: &State<Mutex<Books>>,
+    q: &str,
+    offset: Option<usize>,
+    limit: Option<usize>,
+) -> Result<Json<Page<Book>>, ResponseError> {
+    books
+        .lock()
+        .unwrap()
+        .search_paginated(q, offset.unwrap_or(0), limit.unwrap_or(DEFAULT_LIMIT))
+        .map(Json)
+}
 
-    fn add(&mut self, book: Book) -> Result<(), Error> {
-        let isbn = book.isbn.clone();
-        match self.find(isbn) {
-            Ok(_) => {
-                return Err(Error {
-                    message: "Book already exists in DB".to_string(),
-                });
-            }
-            Err(_) => {
-                self.0.push(book);
-                save_books("books.json", self.0.clone());
-                Ok(())
-            }
-        }
+#[post("/add?<categories>", data = "<isbn>")]
+async fn add(
+    books: &State<Mutex<Books>>,
+    isbn: &str,
+    categories: Option<Vec<String>>,
+) -> Result<Json<Book>, ResponseError> {
+    if !isbn::is_valid(isbn) {
+        return Err(ResponseError::invalid_isbn(isbn));
     }
 
-    fn remove(&mut self, isbn: &str) -> Result<(), Error> {
-        match self.find(isbn.to_string()) {
-            Ok(book) => {
-                let index = self.0.iter().position(|b| b.isbn == book.isbn).unwrap();
-                self.0.remove(index);
-                save_books("books.json", self.0.clone());
-                Ok(())
-            }
-            Err(e) => Err(e),
-        }
-    }
+    let book = google_books::get_book(isbn).await?;
+    let categories = categories.unwrap_or_default();
 
-    fn search(&self, name: &str) -> Result<Vec<Book>, Error> {
-        let found: Vec<Book> = self
-            .0
-            .iter()
-            .filter(|book| book.name.contains(name))
-            .cloned()
-            .collect();
-
-        if found.is_empty() {
-            return Err(Error {
-                message: "No book found".to_string(),
-            });
-        }
-
-        Ok(found)
-    }
+    books
+        .lock()
+        .unwrap()
+        .add(book, &categories)
+        .map(Json)
+}
+
+#[put("/update/<isbn>", data = "<changes>")]
+fn update(
+    books: &State<Mutex<Books>>,
+    isbn: &str,
+    changes: Json<ModifyBook>,
+) -> Result<Json<Book>, ResponseError> {
+    books
+        .lock()
+        .unwrap()
+        .update(isbn, changes.into_inner())
+        .map(Json)
 }
 
-fn load_books(file_path: &str) -> Vec<Book> {
-    let data = fs::read_to_string(file_path).unwrap();
-    serde_json::from_str(&data).unwrap()
+#[post("/remove", data = "<isbn>")]
+fn remove(books: &State<Mutex<Books>>, isbn: &str) -> Result<Json<Book>, ResponseError> {
+    books.lock().unwrap().remove(isbn).map(Json)
 }
 
-fn save_books(file_path: &str, books: Vec<Book>) {
-    let data = serde_json::to_string(&books).unwrap();
-    match fs::write(file_path, data) {
-        Ok(_) => {}
-        Err(e) => {
-            println!("Error writing file: {}", e);
-        }
-    }
+#[post("/borrow/<isbn>", data = "<borrower>")]
+fn borrow(
+    books: &State<Mutex<Books>>,
+    isbn: &str,
+    borrower: &str,
+) -> Result<Json<Loan>, ResponseError> {
+    books.lock().unwrap().borrow(isbn, borrower).map(Json)
 }
 
-async fn get_book(isbn: &str) -> Book {
-    let url = format!(
-        "https://www.googleapis.com/books/v1/volumes?q=isbn:{}",
-        isbn
-    );
-    let response = reqwest::get(&url).await.unwrap().text().await.unwrap();
-
-    let data: Value = serde_json::from_str(&response).unwrap();
-    let items = data["items"].as_array().unwrap();
-    let item = &items[0];
-    let volume_info = item["volumeInfo"].as_object().unwrap();
-
-    let name = volume_info["title"].as_str().unwrap();
-    let author = volume_info["authors"][0].as_str().unwrap();
-
-    Book {
-        isbn: isbn.to_string(),
-        name: name.to_string(),
-        author: author.to_string(),
-    }
+#[post("/return/<isbn>")]
+fn return_book(books: &State<Mutex<Books>>, isbn: &str) -> Result<Json<Loan>, ResponseError> {
+    books.lock().unwrap().return_book(isbn).map(Json)
 }
 
-#[get("/")]
-fn get_all(books: &State<Mutex<Books>>) -> Json<Vec<Book>> {
-    Json(books.lock().unwrap().get_all())
+#[get("/loans")]
+fn loans(books: &State<Mutex<Books>>) -> Json<Vec<Loan>> {
+    Json(books.lock().unwrap().list_loans())
 }
 
-#[get("/get/<isbn>")]
-fn get(books: &State<Mutex<Books>>, isbn: &str) -> Result<Json<Book>, BadRequest<Json<Error>>> {
-    match books.lock().unwrap().find(isbn.to_string()) {
-        Ok(book) => Ok(Json(book)),
-        Err(error) => Err(BadRequest(Some(Json(error)))),
-    }
+#[post("/category/<name>")]
+fn new_category(books: &State<Mutex<Books>>, name: &str) -> Result<(), ResponseError> {
+    books.lock().unwrap().new_category(name)
 }
 
-#[get("/search/<q>")]
-fn search(
-    books: &State<Mutex<Books>>,
-    q: &str,
-) -> Result<Json<Vec<Book>>, BadRequest<Json<Error>>> {
-    match books.lock().unwrap().search(q) {
-        Ok(found) => Ok(Json(found)),
-        Err(error) => Err(BadRequest(Some(Json(error)))),
-    }
+#[delete("/category/<name>")]
+fn del_category(books: &State<Mutex<Books>>, name: &str) -> Result<(), ResponseError> {
+    books.lock().unwrap().del_category(name)
 }
 
-#[post("/add", data = "<isbn>")]
-async fn add(books: &State<Mutex<Books>>, isbn: &str) -> String {
-    let book = get_book(isbn).await;
-    match books.lock().unwrap().add(book) {
-        Ok(()) => {
-            return "Success".to_string();
-        }
-        Err(error) => {
-            return error.message;
-        }
-    }
+#[get("/category/<name>")]
+fn category(books: &State<Mutex<Books>>, name: &str) -> Result<Json<Vec<Book>>, ResponseError> {
+    books.lock().unwrap().books_in_category(name).map(Json)
 }
 
-#[post("/remove", data = "<isbn>")]
-fn remove(books: &State<Mutex<Books>>, isbn: &str) -> String {
-    match books.lock().unwrap().remove(isbn) {
-        Ok(()) => "Success".to_string(),
-        Err(e) => e.message,
-    }
+#[get("/categories")]
+fn categories(books: &State<Mutex<Books>>) -> Json<Vec<String>> {
+    Json(books.lock().unwrap().list_categories())
 }
 
 #[launch]
 fn rocket() -> _ {
-    let books = Books::new();
-
-    rocket::build()
-        .manage(Mutex::new(books))
-        .mount("/", routes![get_all, get, add, remove, search])
+    let config = Config::from_env();
+    let books = Books::new(&config.database_url);
+
+    rocket::build().manage(Mutex::new(books)).manage(config).mount(
+        "/",
+        routes![
+            get_all,
+            get,
+            add,
+            remove,
+            update,
+            search,
+            borrow,
+            return_book,
+            loans,
+            new_category,
+            del_category,
+            category,
+            categories
+        ],
+    )
 }
@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Deserialize, Debug, Serialize, Clone)]
+pub struct Book {
+    pub isbn: String,
+    pub name: String,
+    pub author: String,
+}
+
+/// Partial update for a book: only the fields that are `Some` are applied,
+/// everything else is left untouched.
+#[derive(Deserialize, Debug)]
+pub struct ModifyBook {
+    pub name: Option<String>,
+    pub author: Option<String>,
+    pub categories: Option<Vec<String>>,
+}
+
+/// A page of results along with enough bookkeeping for the caller to page
+/// through the rest of the set.
+#[derive(Serialize, Debug)]
+pub struct Page<T> {
+    pub results: Vec<T>,
+    pub offset: usize,
+    pub limit: usize,
+    pub total: usize,
+}
+
+/// An outstanding loan: who has a book and since when.
+#[derive(Serialize, Debug, Clone)]
+pub struct Loan {
+    pub isbn: String,
+    pub borrower: String,
+    pub borrowed_at: String,
+}